@@ -5,7 +5,10 @@ use std::sync::Arc;
 use eframe::egui::{self, Color32, Frame, Margin, RichText, Rounding, Stroke, Vec2, Visuals};
 use eframe::egui::viewport::IconData;
 use egui_extras::{Column, TableBuilder};
-use crate::{kill_ports, list_ports, parse_port_spec, PortEntry};
+use crate::{
+    kill_port, kill_ports_with, list_ports, parse_port_spec, plan_kills, signal_name, signal_ports, KillOptions,
+    PlannedKill, PortEntry, Protocol, ProtocolFilter, Signal,
+};
 
 // HTML: body background
 const BG_BOTTOM: Color32 = Color32::from_rgb(10, 15, 31); // #0a0f1f
@@ -40,6 +43,18 @@ const REFRESH_TEXT: Color32 = Color32::from_rgb(219, 225, 255);
 // HTML: input background #0f142a
 const INPUT_BG: Color32 = Color32::from_rgb(15, 20, 42);
 
+/// Signals offered in the Kill a Port card. The first three stop the process gracefully
+/// (escalating to SIGKILL after the grace period); the rest are sent without waiting.
+const SIGNAL_CHOICES: &[(Signal, &str)] = &[
+    (Signal::Term, "SIGTERM (graceful)"),
+    (Signal::Interrupt, "SIGINT (Ctrl-C)"),
+    (Signal::Quit, "SIGQUIT"),
+    (Signal::Kill, "SIGKILL (force)"),
+    (Signal::Hangup, "SIGHUP (reload)"),
+    (Signal::User1, "SIGUSR1"),
+    (Signal::User2, "SIGUSR2"),
+];
+
 fn card_frame() -> Frame {
     Frame {
         inner_margin: Margin::symmetric(18.0, 16.0),
@@ -78,7 +93,7 @@ fn load_icon() -> Option<IconData> {
 
 pub fn run() -> anyhow::Result<()> {
     let mut viewport = egui::ViewportBuilder::default()
-        .with_inner_size([720.0, 600.0])
+        .with_inner_size([900.0, 600.0])
         .with_min_inner_size([520.0, 420.0]);
     if let Some(icon) = load_icon() {
         viewport = viewport.with_icon(Arc::new(icon));
@@ -104,6 +119,11 @@ pub fn run() -> anyhow::Result<()> {
     .map_err(|e| anyhow::anyhow!("{}", e))
 }
 
+/// True for the signals the Kill a Port card treats as "stop the process".
+fn is_stop_signal(signal: Signal) -> bool {
+    matches!(signal, Signal::Term | Signal::Interrupt | Signal::Quit | Signal::Kill)
+}
+
 #[derive(Default)]
 struct PortSweeperApp {
     entries: Vec<PortEntry>,
@@ -111,11 +131,21 @@ struct PortSweeperApp {
     status_message: String,
     status_ok: bool,
     status_clear_at: Option<f64>,
+    /// Index into SIGNAL_CHOICES
+    signal_choice: usize,
+    /// Multi-port kill waiting for confirmation in the preview window
+    pending_kill: Option<PendingKill>,
+}
+
+struct PendingKill {
+    ports: Vec<u16>,
+    plan: Vec<PlannedKill>,
 }
 
 impl PortSweeperApp {
     fn refresh(&mut self) {
-        match list_ports() {
+        // The table has a protocol column, so show TCP and UDP listeners together.
+        match list_ports(ProtocolFilter::Both) {
             Ok(e) => self.entries = e,
             Err(e) => {
                 self.status_message = e;
@@ -144,14 +174,49 @@ impl PortSweeperApp {
                 return;
             }
         };
-        let results = kill_ports(&ports);
+        // Several ports: show what would be hit and let the user confirm first
+        if ports.len() > 1 {
+            match plan_kills(&ports, ProtocolFilter::Both, &self.kill_options()) {
+                Ok(plan) if plan.is_empty() => {
+                    self.status_message = "Nothing listening on the given ports".to_string();
+                    self.status_ok = false;
+                    self.status_clear_at = Some(now + 5.0);
+                }
+                Ok(plan) => self.pending_kill = Some(PendingKill { ports, plan }),
+                Err(e) => {
+                    self.status_message = e;
+                    self.status_ok = false;
+                }
+            }
+            return;
+        }
+        self.perform_kill(&ports, now);
+    }
+
+    /// Options for the signal picked in the Kill a Port card.
+    fn kill_options(&self) -> KillOptions {
+        match SIGNAL_CHOICES[self.signal_choice].0 {
+            Signal::Kill => KillOptions { force: true, ..KillOptions::default() },
+            signal => KillOptions { signal, ..KillOptions::default() },
+        }
+    }
+
+    fn perform_kill(&mut self, ports: &[u16], now: f64) {
+        let signal = SIGNAL_CHOICES[self.signal_choice].0;
+        let results = if is_stop_signal(signal) {
+            kill_ports_with(ports, ProtocolFilter::Both, &self.kill_options())
+        } else {
+            signal_ports(ports, ProtocolFilter::Both, signal)
+        };
         let ok: Vec<_> = results.iter().filter(|r| r.success).collect();
         let fail: Vec<_> = results.iter().filter(|r| !r.success).collect();
         if fail.is_empty() {
             self.status_message = if ok.len() == 1 {
                 ok[0].message.clone()
-            } else {
+            } else if is_stop_signal(signal) {
                 format!("{} port(s) terminated successfully!", ok.len())
+            } else {
+                format!("Sent {} to {} port(s)", signal_name(signal), ok.len())
             };
             self.status_ok = true;
             self.port_input.clear();
@@ -167,17 +232,85 @@ impl PortSweeperApp {
         self.status_clear_at = Some(now + 5.0);
     }
 
-    fn kill_port(&mut self, port: u16, now: f64) {
-        let results = kill_ports(&[port]);
-        if let Some(r) = results.first() {
-            self.status_message = r.message.clone();
-            self.status_ok = r.success;
-            if r.success {
-                self.refresh();
+    /// Preview window for a pending multi-port kill: every process that would be signalled,
+    /// with Kill / Cancel.
+    fn show_kill_preview(&mut self, ctx: &egui::Context, now: f64) {
+        let Some(pending) = &self.pending_kill else {
+            return;
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new("Confirm kill")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .frame(card_frame())
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(format!(
+                        "{} process(es) on {} port(s) would be signalled:",
+                        pending.plan.len(),
+                        pending.ports.len()
+                    ))
+                    .size(14.0)
+                    .color(TEXT_MAIN),
+                );
+                ui.add_space(10.0);
+                egui::ScrollArea::vertical().max_height(260.0).show(ui, |ui| {
+                    egui::Grid::new("kill_preview").striped(false).spacing([16.0, 6.0]).show(ui, |ui| {
+                        for label in &["Port", "Proto", "PID", "Process", "User", "Signal"] {
+                            ui.label(RichText::new(*label).size(13.0).color(HEADER_COLOR));
+                        }
+                        ui.end_row();
+                        for p in &pending.plan {
+                            ui.label(p.port.to_string());
+                            ui.label(RichText::new(p.protocol.to_string()).color(SUBTITLE_COLOR));
+                            ui.label(p.pid.to_string());
+                            ui.label(&p.process_name);
+                            ui.label(RichText::new(&p.user).color(SUBTITLE_COLOR));
+                            ui.label(signal_name(p.signal));
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.add_space(14.0);
+                ui.horizontal(|ui| {
+                    let kill_btn = egui::Button::new(
+                        RichText::new(format!("Kill {} process(es)", pending.plan.len()))
+                            .size(14.0)
+                            .color(Color32::WHITE),
+                    )
+                    .fill(RED_TOP)
+                    .stroke(Stroke::new(1.0, RED_BOTTOM))
+                    .rounding(Rounding::same(8.0))
+                    .min_size(Vec2::new(140.0, 34.0));
+                    confirmed = ui.add(kill_btn).clicked();
+                    let cancel_btn = egui::Button::new(RichText::new("Cancel").size(14.0).color(REFRESH_TEXT))
+                        .fill(REFRESH_BG_TOP)
+                        .stroke(Stroke::new(1.0, REFRESH_BORDER))
+                        .rounding(Rounding::same(8.0))
+                        .min_size(Vec2::new(100.0, 34.0));
+                    cancelled = ui.add(cancel_btn).clicked();
+                });
+            });
+        if confirmed {
+            if let Some(pending) = self.pending_kill.take() {
+                self.perform_kill(&pending.ports, now);
             }
-            self.status_clear_at = Some(now + 5.0);
+        } else if cancelled {
+            self.pending_kill = None;
         }
     }
+
+    fn kill_port(&mut self, port: u16, protocol: Protocol, now: f64) {
+        let r = kill_port(port, protocol);
+        self.status_message = r.message;
+        self.status_ok = r.success;
+        if r.success {
+            self.refresh();
+        }
+        self.status_clear_at = Some(now + 5.0);
+    }
 }
 
 impl eframe::App for PortSweeperApp {
@@ -193,6 +326,8 @@ impl eframe::App for PortSweeperApp {
             }
         }
 
+        self.show_kill_preview(ctx, now);
+
         egui::CentralPanel::default().show(ctx, |ui| {
             let viewport = ui.ctx().screen_rect();
             ui.painter().rect_filled(viewport, 0.0, BG_BOTTOM);
@@ -217,8 +352,8 @@ impl eframe::App for PortSweeperApp {
                 );
                 ui.add_space(18.0);
 
-                // ── Table (HTML .table): grid 80px 1fr 90px 110px 110px, header 13px #8c94c8 ─────
-                let mut kill_port: Option<u16> = None;
+                // ── Table (HTML .table): grid 80px 70px 130px 1fr 90px 110px 110px, header 13px #8c94c8 ─────
+                let mut kill_target: Option<(u16, Protocol)> = None;
                 // Reserve space for Refresh button, Kill a Port card, status, and padding
                 const RESERVED_BELOW_TABLE: f32 = 260.0;
                 const MIN_TABLE_HEIGHT: f32 = 120.0;
@@ -233,12 +368,14 @@ impl eframe::App for PortSweeperApp {
                         .max_scroll_height(table_max_height)
                         .scroll_bar_visibility(egui::containers::scroll_area::ScrollBarVisibility::AlwaysVisible)
                         .column(Column::exact(80.0))
+                        .column(Column::exact(70.0))
+                        .column(Column::exact(130.0))
                         .column(Column::remainder().at_least(120.0))
                         .column(Column::exact(90.0))
                         .column(Column::exact(110.0))
                         .column(Column::exact(110.0))
                         .header(28.0, |mut h| {
-                            for label in &["Port", "Process", "PID", "Status", "Action"] {
+                            for label in &["Port", "Proto", "Address", "Process", "PID", "Status", "Action"] {
                                 h.col(|ui| {
                                     ui.label(
                                         RichText::new(*label).size(13.0).color(HEADER_COLOR),
@@ -253,6 +390,13 @@ impl eframe::App for PortSweeperApp {
                                 row.col(|ui| {
                                     ui.label(RichText::new(e.port.to_string()).size(14.0).color(TEXT_MAIN));
                                 });
+                                row.col(|ui| {
+                                    ui.label(RichText::new(e.protocol.to_string()).size(14.0).color(SUBTITLE_COLOR));
+                                });
+                                row.col(|ui| {
+                                    ui.label(RichText::new(e.bind_addr.to_string()).size(14.0).color(SUBTITLE_COLOR))
+                                        .on_hover_text(e.bind_addr.to_string());
+                                });
                                 row.col(|ui| {
                                     ui.label(RichText::new(&e.process_name).size(14.0).color(TEXT_MAIN));
                                 });
@@ -277,7 +421,7 @@ impl eframe::App for PortSweeperApp {
                                         ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
                                     }
                                     if response.clicked() {
-                                        kill_port = Some(e.port);
+                                        kill_target = Some((e.port, e.protocol));
                                     }
                                 });
                             });
@@ -285,8 +429,8 @@ impl eframe::App for PortSweeperApp {
                     });
                 });
 
-                if let Some(port) = kill_port {
-                    self.kill_port(port, now);
+                if let Some((port, protocol)) = kill_target {
+                    self.kill_port(port, protocol, now);
                 }
 
                 // HTML: .refresh margin 8px 0 20px, padding 10px 16px, border-radius 8px (text centered, shadow, scale on hover)
@@ -331,7 +475,7 @@ impl eframe::App for PortSweeperApp {
                     );
                     ui.add_space(14.0);
                     ui.horizontal(|ui| {
-                        let input_w = ui.available_width() - 120.0 - ui.spacing().item_spacing.x;
+                        let input_w = ui.available_width() - 120.0 - 170.0 - 2.0 * ui.spacing().item_spacing.x;
                         let input_frame = Frame::none()
                             .fill(INPUT_BG)
                             .rounding(Rounding::same(8.0))
@@ -351,8 +495,21 @@ impl eframe::App for PortSweeperApp {
                                     .vertical_align(egui::Align::Center),
                             );
                         });
+                        egui::ComboBox::from_id_salt("signal_choice")
+                            .width(160.0)
+                            .selected_text(SIGNAL_CHOICES[self.signal_choice].1)
+                            .show_ui(ui, |ui| {
+                                for (i, (_, label)) in SIGNAL_CHOICES.iter().enumerate() {
+                                    ui.selectable_value(&mut self.signal_choice, i, *label);
+                                }
+                            });
+                        let action = if is_stop_signal(SIGNAL_CHOICES[self.signal_choice].0) {
+                            "Kill Port"
+                        } else {
+                            "Send Signal"
+                        };
                         let kill_btn = egui::Button::new(
-                            RichText::new("Kill Port").size(14.0).color(Color32::WHITE),
+                            RichText::new(action).size(14.0).color(Color32::WHITE),
                         )
                         .fill(RED_TOP)
                         .stroke(Stroke::new(1.0, RED_BOTTOM))
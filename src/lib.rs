@@ -1,25 +1,160 @@
 //! Core library for Port Sweeper: list processes by port, kill by port, parse port specs.
 
-use listeners::{Protocol, get_all, get_process_by_port};
-use std::collections::BTreeSet;
-use std::net::IpAddr;
-use sysinfo::{Pid, System};
+use listeners::get_all;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System, UpdateKind, Users};
+
+pub use sysinfo::Signal;
+
+/// Transport protocol of a listening socket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Protocol::Tcp => f.pad("TCP"),
+            Protocol::Udp => f.pad("UDP"),
+        }
+    }
+}
+
+impl From<listeners::Protocol> for Protocol {
+    fn from(p: listeners::Protocol) -> Self {
+        match p {
+            listeners::Protocol::TCP => Protocol::Tcp,
+            listeners::Protocol::UDP => Protocol::Udp,
+        }
+    }
+}
+
+impl From<Protocol> for listeners::Protocol {
+    fn from(p: Protocol) -> Self {
+        match p {
+            Protocol::Tcp => listeners::Protocol::TCP,
+            Protocol::Udp => listeners::Protocol::UDP,
+        }
+    }
+}
+
+/// Which protocols to include when listing or killing. Parses from "tcp", "udp" or "both".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProtocolFilter {
+    #[default]
+    Tcp,
+    Udp,
+    Both,
+}
+
+impl ProtocolFilter {
+    /// The protocols this filter selects, TCP first.
+    pub fn protocols(self) -> &'static [Protocol] {
+        match self {
+            ProtocolFilter::Tcp => &[Protocol::Tcp],
+            ProtocolFilter::Udp => &[Protocol::Udp],
+            ProtocolFilter::Both => &[Protocol::Tcp, Protocol::Udp],
+        }
+    }
+
+    pub fn includes(self, protocol: Protocol) -> bool {
+        self.protocols().contains(&protocol)
+    }
+}
+
+impl From<Protocol> for ProtocolFilter {
+    fn from(p: Protocol) -> Self {
+        match p {
+            Protocol::Tcp => ProtocolFilter::Tcp,
+            Protocol::Udp => ProtocolFilter::Udp,
+        }
+    }
+}
+
+impl FromStr for ProtocolFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "tcp" => Ok(ProtocolFilter::Tcp),
+            "udp" => Ok(ProtocolFilter::Udp),
+            "both" | "all" => Ok(ProtocolFilter::Both),
+            other => Err(format!("invalid protocol: {} (expected tcp, udp or both)", other)),
+        }
+    }
+}
 
 /// A row for display: one listening port with process info.
 #[derive(Debug, Clone)]
 pub struct PortEntry {
     pub port: u16,
+    pub protocol: Protocol,
+    /// Local address the socket is bound to (0.0.0.0 / :: for all interfaces).
+    pub bind_addr: IpAddr,
     pub process_name: String,
     pub pid: u32,
     pub status: String,
 }
 
-/// Result of killing a single port.
+/// Result of killing a single port. `success` is true only if every owning process was killed.
 #[derive(Debug, Clone)]
 pub struct KillResult {
     pub port: u16,
+    pub protocol: Protocol,
+    pub success: bool,
+    pub message: String,
+    /// One entry per process that held the port (empty if nothing was listening).
+    pub processes: Vec<ProcessKill>,
+}
+
+/// Outcome of killing one of the processes holding a port.
+#[derive(Debug, Clone)]
+pub struct ProcessKill {
+    pub pid: u32,
+    pub process_name: String,
     pub success: bool,
     pub message: String,
+    /// The signal that finally stopped the process (the requested one, or SIGKILL after
+    /// escalation). For [`signal_port`], the signal that was delivered.
+    pub signal: Option<Signal>,
+}
+
+/// One process a kill would hit, as reported by [`plan_kills`] without touching anything.
+#[derive(Debug, Clone)]
+pub struct PlannedKill {
+    pub port: u16,
+    pub protocol: Protocol,
+    pub pid: u32,
+    pub process_name: String,
+    /// Owning user name (or numeric uid if it has no name; empty if unknown).
+    pub user: String,
+    /// First signal the process would receive.
+    pub signal: Signal,
+}
+
+/// How to stop the processes on a port.
+#[derive(Debug, Clone)]
+pub struct KillOptions {
+    /// First signal to send; SIGTERM unless the process expects something else (e.g. SIGINT).
+    pub signal: Signal,
+    /// How long to wait after `signal` before escalating to SIGKILL.
+    pub grace: Duration,
+    /// Skip `signal` and send SIGKILL right away.
+    pub force: bool,
+    /// Also stop the process tree around each listener (see [`port_tree`]).
+    pub tree: Option<TreeScope>,
+}
+
+impl Default for KillOptions {
+    fn default() -> Self {
+        KillOptions { signal: Signal::Term, grace: Duration::from_secs(3), force: false, tree: None }
+    }
 }
 
 /// Parse a port spec string into a list of port numbers.
@@ -62,72 +197,662 @@ pub fn parse_port_spec(s: &str) -> Result<Vec<u16>, String> {
     Ok(ports.into_iter().collect())
 }
 
-/// List all listening ports with process info. Deduped by (port, pid).
-/// Filters to TCP listeners (and optionally UDP); only includes bindings that have a port.
-pub fn list_ports() -> Result<Vec<PortEntry>, String> {
+/// Parse a duration like "500ms", "3s", "1.5s", "2m" or "1h". A bare number is seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(s.len());
+    let (num, unit) = s.split_at(split);
+    let value: f64 = num.parse().map_err(|_| format!("invalid duration: {}", s))?;
+    let secs = match unit.trim() {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => return Err(format!("invalid duration unit in {} (use ms, s, m or h)", s)),
+    };
+    Duration::try_from_secs_f64(secs).map_err(|_| format!("invalid duration: {}", s))
+}
+
+/// Conventional name for a signal, e.g. "SIGTERM".
+pub fn signal_name(signal: Signal) -> &'static str {
+    match signal {
+        Signal::Hangup => "SIGHUP",
+        Signal::Interrupt => "SIGINT",
+        Signal::Quit => "SIGQUIT",
+        Signal::Illegal => "SIGILL",
+        Signal::Trap => "SIGTRAP",
+        Signal::Abort => "SIGABRT",
+        Signal::IOT => "SIGIOT",
+        Signal::Bus => "SIGBUS",
+        Signal::FloatingPointException => "SIGFPE",
+        Signal::Kill => "SIGKILL",
+        Signal::User1 => "SIGUSR1",
+        Signal::Segv => "SIGSEGV",
+        Signal::User2 => "SIGUSR2",
+        Signal::Pipe => "SIGPIPE",
+        Signal::Alarm => "SIGALRM",
+        Signal::Term => "SIGTERM",
+        Signal::Child => "SIGCHLD",
+        Signal::Continue => "SIGCONT",
+        Signal::Stop => "SIGSTOP",
+        Signal::TSTP => "SIGTSTP",
+        Signal::TTIN => "SIGTTIN",
+        Signal::TTOU => "SIGTTOU",
+        Signal::Urgent => "SIGURG",
+        Signal::XCPU => "SIGXCPU",
+        Signal::XFSZ => "SIGXFSZ",
+        Signal::VirtualAlarm => "SIGVTALRM",
+        Signal::Profiling => "SIGPROF",
+        Signal::Winch => "SIGWINCH",
+        Signal::IO => "SIGIO",
+        Signal::Poll => "SIGPOLL",
+        Signal::Power => "SIGPWR",
+        Signal::Sys => "SIGSYS",
+    }
+}
+
+/// Parse a signal name: "INT", "SIGINT" or "int"; or one of the numbers that are the same
+/// on every Unix (1 HUP, 2 INT, 3 QUIT, 6 ABRT, 9 KILL, 14 ALRM, 15 TERM).
+pub fn parse_signal(s: &str) -> Result<Signal, String> {
+    let upper = s.trim().to_ascii_uppercase();
+    let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+    let signal = match name {
+        "1" => Signal::Hangup,
+        "2" => Signal::Interrupt,
+        "3" => Signal::Quit,
+        "6" => Signal::Abort,
+        "9" => Signal::Kill,
+        "14" => Signal::Alarm,
+        "15" => Signal::Term,
+        _ => SIGNALS
+            .iter()
+            .copied()
+            .find(|&sig| &signal_name(sig)[3..] == name)
+            .ok_or_else(|| format!("unknown signal: {}", s.trim()))?,
+    };
+    Ok(signal)
+}
+
+/// Every signal [`parse_signal`] knows by name.
+const SIGNALS: &[Signal] = &[
+    Signal::Hangup,
+    Signal::Interrupt,
+    Signal::Quit,
+    Signal::Illegal,
+    Signal::Trap,
+    Signal::Abort,
+    Signal::IOT,
+    Signal::Bus,
+    Signal::FloatingPointException,
+    Signal::Kill,
+    Signal::User1,
+    Signal::Segv,
+    Signal::User2,
+    Signal::Pipe,
+    Signal::Alarm,
+    Signal::Term,
+    Signal::Child,
+    Signal::Continue,
+    Signal::Stop,
+    Signal::TSTP,
+    Signal::TTIN,
+    Signal::TTOU,
+    Signal::Urgent,
+    Signal::XCPU,
+    Signal::XFSZ,
+    Signal::VirtualAlarm,
+    Signal::Profiling,
+    Signal::Winch,
+    Signal::IO,
+    Signal::Poll,
+    Signal::Power,
+    Signal::Sys,
+];
+
+/// List all listening ports with process info. One row per (bind address, port, protocol, pid),
+/// covering wildcard, loopback and interface-specific bindings alike.
+/// Only includes protocols selected by `protocols` and bindings that have a port.
+pub fn list_ports(protocols: ProtocolFilter) -> Result<Vec<PortEntry>, String> {
     let listeners = get_all().map_err(|e| e.to_string())?;
-    let mut seen = BTreeSet::<(u16, u32)>::new();
+    let tcp_listening = if protocols.includes(Protocol::Tcp) {
+        tcp_listening_addrs()?
+    } else {
+        None
+    };
+    let mut seen = BTreeSet::<(IpAddr, u16, Protocol, u32)>::new();
     let mut entries = Vec::new();
     for l in listeners {
-        if l.protocol != Protocol::TCP {
+        let protocol = Protocol::from(l.protocol);
+        if !protocols.includes(protocol) {
             continue;
         }
         let port = l.socket.port();
         if port == 0 {
             continue;
         }
-        // Only include "listening" bindings: 0.0.0.0 or :: or similar
-        let ip = l.socket.ip();
-        if !matches!(ip, IpAddr::V4(a) if a.is_unspecified())
-            && !matches!(ip, IpAddr::V6(a) if a.is_unspecified())
-        {
+        // The listeners crate reports TCP sockets in every state; keep only listening ones.
+        if protocol == Protocol::Tcp && !is_tcp_listener(&l.socket, tcp_listening.as_ref()) {
             continue;
         }
-        if seen.insert((port, l.process.pid)) {
+        let bind_addr = l.socket.ip();
+        if seen.insert((bind_addr, port, protocol, l.process.pid)) {
             entries.push(PortEntry {
                 port,
+                protocol,
+                bind_addr,
                 process_name: l.process.name.clone(),
                 pid: l.process.pid,
                 status: "Running".to_string(),
             });
         }
     }
-    entries.sort_by_key(|e| (e.port, e.pid));
+    entries.sort_by_key(|e| (e.port, e.protocol, e.bind_addr, e.pid));
     Ok(entries)
 }
 
-/// Kill the process listening on the given port. Returns (success, message).
-pub fn kill_port(port: u16) -> (bool, String) {
-    let process = match get_process_by_port(port, Protocol::TCP) {
-        Ok(p) => p,
-        Err(e) => return (false, format!("Port {}: {}", port, e)),
+/// Local addresses of TCP sockets in LISTEN state, where the platform exposes socket state.
+#[cfg(target_os = "linux")]
+fn tcp_listening_addrs() -> Result<Option<BTreeSet<SocketAddr>>, String> {
+    let rows = proc_net::read_tables(std::path::Path::new("/proc/net"), Protocol::Tcp)?;
+    Ok(Some(
+        rows.into_iter()
+            .filter(|r| r.state == proc_net::TCP_LISTEN)
+            .map(|r| r.local)
+            .collect(),
+    ))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn tcp_listening_addrs() -> Result<Option<BTreeSet<SocketAddr>>, String> {
+    Ok(None)
+}
+
+/// Without socket state, treat wildcard bindings as listeners and drop specific-address
+/// bindings on dynamic ports (49152-65535), which are almost always outgoing connections.
+fn is_tcp_listener(socket: &SocketAddr, listening: Option<&BTreeSet<SocketAddr>>) -> bool {
+    match listening {
+        Some(set) => set.contains(socket),
+        None => socket.ip().is_unspecified() || socket.port() < 49152,
+    }
+}
+
+/// "Port 3000" for TCP, "Port 3000/udp" for UDP, so the common case reads as before.
+fn port_label(port: u16, protocol: Protocol) -> String {
+    match protocol {
+        Protocol::Tcp => format!("Port {}", port),
+        Protocol::Udp => format!("Port {}/udp", port),
+    }
+}
+
+/// Listening sockets captured once for a whole batch: (port, protocol) -> owning pids.
+/// Several processes can share a port: SO_REUSEPORT workers, prefork servers whose workers
+/// inherit the listening socket, or separate IPv4 and IPv6 listeners.
+struct Snapshot {
+    owners: BTreeMap<(u16, Protocol), Vec<u32>>,
+}
+
+impl Snapshot {
+    /// One pass over /proc/net and one scan of /proc/*/fd, however many ports are asked about.
+    #[cfg(target_os = "linux")]
+    fn take(protocols: ProtocolFilter) -> Result<Snapshot, String> {
+        let mut sockets = Vec::new();
+        for &protocol in protocols.protocols() {
+            for r in proc_net::read_tables(std::path::Path::new("/proc/net"), protocol)? {
+                let listening = protocol == Protocol::Udp || r.state == proc_net::TCP_LISTEN;
+                if listening && r.local.port() != 0 {
+                    sockets.push((r.local.port(), protocol, r.inode));
+                }
+            }
+        }
+        let inodes: BTreeSet<u64> = sockets.iter().map(|s| s.2).collect();
+        let by_inode = proc_net::socket_owners(std::path::Path::new("/proc"), &inodes);
+        let mut owners = BTreeMap::<(u16, Protocol), Vec<u32>>::new();
+        for (port, protocol, inode) in sockets {
+            for &pid in by_inode.get(&inode).into_iter().flatten() {
+                let pids = owners.entry((port, protocol)).or_default();
+                if !pids.contains(&pid) {
+                    pids.push(pid);
+                }
+            }
+        }
+        for pids in owners.values_mut() {
+            pids.sort_unstable();
+        }
+        Ok(Snapshot { owners })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn take(protocols: ProtocolFilter) -> Result<Snapshot, String> {
+        let listeners = get_all().map_err(|e| e.to_string())?;
+        let mut owners = BTreeMap::<(u16, Protocol), Vec<u32>>::new();
+        for l in listeners {
+            let protocol = Protocol::from(l.protocol);
+            let port = l.socket.port();
+            if !protocols.includes(protocol) || port == 0 {
+                continue;
+            }
+            if protocol == Protocol::Tcp && !is_tcp_listener(&l.socket, None) {
+                continue;
+            }
+            let pids = owners.entry((port, protocol)).or_default();
+            if !pids.contains(&l.process.pid) {
+                pids.push(l.process.pid);
+            }
+        }
+        for pids in owners.values_mut() {
+            pids.sort_unstable();
+        }
+        Ok(Snapshot { owners })
+    }
+
+    fn owners(&self, port: u16, protocol: Protocol) -> &[u32] {
+        self.owners.get(&(port, protocol)).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+/// One port and protocol to act on, and the pids to signal for it (empty: nothing listening).
+struct Target {
+    port: u16,
+    protocol: Protocol,
+    pids: Result<Vec<u32>, String>,
+}
+
+impl Target {
+    fn result(&self, success: bool, message: String, processes: Vec<ProcessKill>) -> KillResult {
+        KillResult { port: self.port, protocol: self.protocol, success, message, processes }
+    }
+}
+
+/// Resolve `ports` against one snapshot. With [`ProtocolFilter::Both`], a protocol with nothing
+/// listening on a port is left out, unless neither protocol has a listener (then a single empty
+/// TCP target reports it). A free port costs a map lookup, nothing more.
+fn resolve_targets(snapshot: &Snapshot, ports: &[u16], protocols: ProtocolFilter) -> Vec<Target> {
+    let mut targets = Vec::new();
+    for &port in ports {
+        let before = targets.len();
+        for &protocol in protocols.protocols() {
+            let pids = snapshot.owners(port, protocol);
+            if protocols == ProtocolFilter::Both && pids.is_empty() {
+                continue;
+            }
+            targets.push(Target { port, protocol, pids: Ok(pids.to_vec()) });
+        }
+        if targets.len() == before {
+            targets.push(Target { port, protocol: protocols.protocols()[0], pids: Ok(Vec::new()) });
+        }
+    }
+    targets
+}
+
+/// Take a snapshot and resolve `ports`; if the snapshot fails, every port reports the error.
+fn snapshot_targets(ports: &[u16], protocols: ProtocolFilter) -> Vec<Target> {
+    match Snapshot::take(protocols) {
+        Ok(snapshot) => resolve_targets(&snapshot, ports, protocols),
+        Err(e) => ports
+            .iter()
+            .map(|&port| Target { port, protocol: protocols.protocols()[0], pids: Err(e.clone()) })
+            .collect(),
+    }
+}
+
+/// Every pid across `targets`, in target order, without duplicates.
+fn target_pids(targets: &[Target]) -> Vec<u32> {
+    let mut seen = BTreeSet::new();
+    targets
+        .iter()
+        .filter_map(|t| t.pids.as_ref().ok())
+        .flatten()
+        .copied()
+        .filter(|&pid| seen.insert(pid))
+        .collect()
+}
+
+/// One targeted process refresh for a batch: only `pids`, or every process when the trees
+/// around them are needed.
+fn load_processes(pids: &[u32], everything: bool) -> System {
+    let mut sys = System::new();
+    let kind = ProcessRefreshKind::new().with_user(UpdateKind::OnlyIfNotSet);
+    if everything {
+        sys.refresh_processes_specifics(ProcessesToUpdate::All, true, kind);
+    } else {
+        let pids: Vec<Pid> = pids.iter().map(|&p| Pid::from_u32(p)).collect();
+        sys.refresh_processes_specifics(ProcessesToUpdate::Some(&pids), true, kind);
+    }
+    sys
+}
+
+/// Replace each target's pids with the tree around them. Parents come first, so a supervisor
+/// is stopped before it can respawn a child.
+fn expand_targets(sys: &System, targets: &mut [Target], scope: TreeScope) {
+    for t in targets.iter_mut() {
+        if let Ok(pids) = &t.pids {
+            if !pids.is_empty() {
+                t.pids = tree::expand(sys, pids, scope).map(|tree| tree.into_iter().map(|p| p.pid).collect());
+            }
+        }
+    }
+}
+
+fn process_name(sys: &System, pid: u32) -> String {
+    sys.process(Pid::from_u32(pid))
+        .map(|p| p.name().to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// The processes [`kill_port_with`] would stop on this port with `tree: Some(scope)`, parents
+/// before children, so the whole tree can be reviewed before acting.
+pub fn port_tree(port: u16, protocol: Protocol, scope: TreeScope) -> Result<Vec<TreeProcess>, String> {
+    let snapshot = Snapshot::take(protocol.into())?;
+    let pids = snapshot.owners(port, protocol);
+    if pids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let sys = load_processes(pids, true);
+    tree::expand(&sys, pids, scope)
+}
+
+/// Dry run of [`kill_ports_with`]: resolve each port to the process(es) that would be
+/// signalled (including the tree with `opts.tree`) and return the plan. Ports with nothing
+/// listening have no entries.
+pub fn plan_kills(ports: &[u16], protocols: ProtocolFilter, opts: &KillOptions) -> Result<Vec<PlannedKill>, String> {
+    let snapshot = Snapshot::take(protocols)?;
+    let mut targets = resolve_targets(&snapshot, ports, protocols);
+    let sys = load_processes(&target_pids(&targets), opts.tree.is_some());
+    if let Some(scope) = opts.tree {
+        expand_targets(&sys, &mut targets, scope);
+    }
+    let users = Users::new_with_refreshed_list();
+    let signal = if opts.force { Signal::Kill } else { opts.signal };
+    let mut plan = Vec::new();
+    for t in targets {
+        for pid in t.pids? {
+            let user = sys
+                .process(Pid::from_u32(pid))
+                .and_then(|p| p.user_id())
+                .map(|uid| match users.get_user_by_id(uid) {
+                    Some(u) => u.name().to_string(),
+                    None => uid.to_string(),
+                })
+                .unwrap_or_default();
+            plan.push(PlannedKill {
+                port: t.port,
+                protocol: t.protocol,
+                pid,
+                process_name: process_name(&sys, pid),
+                user,
+                signal,
+            });
+        }
+    }
+    Ok(plan)
+}
+
+/// Stop every process listening on the given port and protocol, gracefully by default.
+pub fn kill_port(port: u16, protocol: Protocol) -> KillResult {
+    kill_port_with(port, protocol, &KillOptions::default())
+}
+
+/// Stop every process listening on the given port and protocol: send `opts.signal`, wait up to
+/// `opts.grace` for the processes to exit or the port to close, then SIGKILL what is left.
+/// With `opts.force`, SIGKILL is sent right away.
+pub fn kill_port_with(port: u16, protocol: Protocol, opts: &KillOptions) -> KillResult {
+    // A single protocol always resolves to exactly one target per port
+    kill_ports_with(&[port], protocol.into(), opts).remove(0)
+}
+
+/// Stop processes on all ports in the spec, gracefully. See [`kill_ports_with`].
+pub fn kill_ports(ports: &[u16], protocols: ProtocolFilter) -> Vec<KillResult> {
+    kill_ports_with(ports, protocols, &KillOptions::default())
+}
+
+/// Stop processes on all ports in the spec. Returns one result per port and protocol.
+/// The whole batch shares one socket snapshot and one process refresh: every process is
+/// signalled, then all of them are waited on together for up to `opts.grace` before the
+/// survivors get SIGKILL. Ports with nothing listening report so without further work.
+/// With [`ProtocolFilter::Both`], a protocol with nothing listening on the port is left out,
+/// unless neither protocol has a listener (then a single TCP failure is reported).
+pub fn kill_ports_with(ports: &[u16], protocols: ProtocolFilter, opts: &KillOptions) -> Vec<KillResult> {
+    let mut targets = snapshot_targets(ports, protocols);
+    let mut sys = load_processes(&target_pids(&targets), opts.tree.is_some());
+    if let Some(scope) = opts.tree {
+        expand_targets(&sys, &mut targets, scope);
+    }
+    let watched: Vec<(u16, Protocol)> = targets
+        .iter()
+        .filter(|t| t.pids.as_ref().is_ok_and(|p| !p.is_empty()))
+        .map(|t| (t.port, t.protocol))
+        .collect();
+    let still_listening = || {
+        let snapshot = Snapshot::take(protocols).ok()?;
+        Some(watched.iter().flat_map(|&(port, protocol)| snapshot.owners(port, protocol).to_vec()).collect())
     };
-    let mut sys = System::new_all();
-    sys.refresh_all();
-    let pid = Pid::from_u32(process.pid);
-    let proc_ref = match sys.process(pid) {
-        Some(p) => p,
-        None => return (false, format!("Port {}: process {} not found", port, process.pid)),
+    let outcomes = terminate(&mut sys, &target_pids(&targets), opts, still_listening);
+    targets.iter().map(|t| kill_summary(t, &outcomes, opts)).collect()
+}
+
+/// Per-port result of a batch kill, from the per-pid outcomes.
+fn kill_summary(target: &Target, outcomes: &BTreeMap<u32, ProcessKill>, opts: &KillOptions) -> KillResult {
+    let label = port_label(target.port, target.protocol);
+    let pids = match &target.pids {
+        Ok(p) if p.is_empty() => return target.result(false, format!("{}: nothing listening", label), Vec::new()),
+        Ok(p) => p,
+        Err(e) => return target.result(false, format!("{}: {}", label, e), Vec::new()),
     };
-    if proc_ref.kill() {
-        (true, format!("Port {} terminated successfully!", port))
+    let processes: Vec<ProcessKill> = pids.iter().filter_map(|pid| outcomes.get(pid)).cloned().collect();
+    let killed = processes.iter().filter(|p| p.success).count();
+    let escalated = !opts.force && processes.iter().any(|p| p.signal == Some(Signal::Kill));
+    let success = killed == processes.len();
+    let message = if success && processes.len() == 1 && escalated {
+        format!("{} terminated successfully! (escalated to SIGKILL)", label)
+    } else if success && processes.len() == 1 {
+        format!("{} terminated successfully!", label)
+    } else if success && escalated {
+        format!("{} terminated successfully! ({} processes, escalated to SIGKILL)", label, killed)
+    } else if success {
+        format!("{} terminated successfully! ({} processes)", label, killed)
+    } else if processes.len() == 1 {
+        format!("{}: {}", label, processes[0].message)
     } else {
-        (false, format!("Port {}: failed to kill process (try running with elevated permissions)", port))
+        let failures: Vec<String> = processes
+            .iter()
+            .filter(|p| !p.success)
+            .map(|p| format!("pid {}: {}", p.pid, p.message))
+            .collect();
+        format!(
+            "{}: killed {} of {} processes ({})",
+            label,
+            killed,
+            processes.len(),
+            failures.join(", ")
+        )
+    };
+    target.result(success, message, processes)
+}
+
+/// Signal `pids` per `opts` and wait for them together, returning one outcome per pid.
+/// `still_listening` re-snapshots the targeted ports and returns the pids still holding them;
+/// unless a whole tree is being stopped, a process that has released its port is left to
+/// finish shutting down instead of being escalated.
+fn terminate(
+    sys: &mut System,
+    pids: &[u32],
+    opts: &KillOptions,
+    still_listening: impl Fn() -> Option<BTreeSet<u32>>,
+) -> BTreeMap<u32, ProcessKill> {
+    const POLL: Duration = Duration::from_millis(50);
+    const PORT_CHECK: Duration = Duration::from_millis(250);
+    let mut outcomes: BTreeMap<u32, ProcessKill> = pids
+        .iter()
+        .map(|&pid| {
+            let outcome = ProcessKill {
+                pid,
+                process_name: process_name(sys, pid),
+                success: false,
+                message: String::new(),
+                signal: None,
+            };
+            (pid, outcome)
+        })
+        .collect();
+    // Pids that got the first signal and have not exited yet, in signalling order
+    let mut pending = Vec::new();
+    for &pid in pids {
+        let outcome = outcomes.get_mut(&pid).expect("outcome for every pid");
+        let Some(proc_ref) = sys.process(Pid::from_u32(pid)) else {
+            outcome.message = format!("process {} not found", pid);
+            continue;
+        };
+        // kill_with returns None where the signal does not exist (Windows): kill outright there
+        let sent = if opts.force { None } else { proc_ref.kill_with(opts.signal) };
+        match sent {
+            Some(true) => pending.push(pid),
+            Some(false) => outcome.message = kill_failed_message(),
+            None => {
+                if proc_ref.kill() {
+                    outcome.success = true;
+                    outcome.signal = Some(Signal::Kill);
+                    outcome.message = "killed (SIGKILL)".to_string();
+                } else {
+                    outcome.message = kill_failed_message();
+                }
+            }
+        }
+    }
+
+    let deadline = Instant::now() + opts.grace;
+    let mut last_port_check = Instant::now();
+    let mut released = BTreeSet::new();
+    while !pending.is_empty() {
+        let watched: Vec<Pid> = pending.iter().map(|&pid| Pid::from_u32(pid)).collect();
+        sys.refresh_processes(ProcessesToUpdate::Some(&watched), true);
+        pending.retain(|&pid| {
+            let exited = match sys.process(Pid::from_u32(pid)) {
+                None => true,
+                Some(p) => matches!(p.status(), ProcessStatus::Zombie | ProcessStatus::Dead),
+            };
+            if exited {
+                let outcome = outcomes.get_mut(&pid).expect("outcome for every pid");
+                outcome.success = true;
+                outcome.signal = Some(opts.signal);
+                outcome.message = format!("terminated ({})", signal_name(opts.signal));
+            }
+            !exited
+        });
+        if pending.is_empty() || Instant::now() >= deadline {
+            break;
+        }
+        if opts.tree.is_none() && last_port_check.elapsed() >= PORT_CHECK {
+            last_port_check = Instant::now();
+            if let Some(listening) = still_listening() {
+                if pending.iter().all(|pid| !listening.contains(pid)) {
+                    released.extend(pending.iter().copied());
+                    break;
+                }
+            }
+        }
+        std::thread::sleep(POLL);
+    }
+    if opts.tree.is_none() && !pending.is_empty() && released.is_empty() {
+        if let Some(listening) = still_listening() {
+            released.extend(pending.iter().copied().filter(|pid| !listening.contains(pid)));
+        }
+    }
+
+    for pid in pending {
+        let outcome = outcomes.get_mut(&pid).expect("outcome for every pid");
+        if released.contains(&pid) {
+            outcome.success = true;
+            outcome.signal = Some(opts.signal);
+            outcome.message =
+                format!("port released ({}), process still shutting down", signal_name(opts.signal));
+            continue;
+        }
+        let killed = sys.process(Pid::from_u32(pid)).map(|p| p.kill()).unwrap_or(true);
+        if killed {
+            outcome.success = true;
+            outcome.signal = Some(Signal::Kill);
+            outcome.message = format!(
+                "killed (SIGKILL after {:.1}s grace period)",
+                opts.grace.as_secs_f64()
+            );
+        } else {
+            outcome.message = kill_failed_message();
+        }
     }
+    outcomes
 }
 
-/// Kill processes on all ports in the spec. Returns one result per port.
-pub fn kill_ports(ports: &[u16]) -> Vec<KillResult> {
-    ports
+fn kill_failed_message() -> String {
+    "failed to kill process (try running with elevated permissions)".to_string()
+}
+
+/// Send `signal` to every process listening on the given port and protocol, without waiting
+/// for them to exit (e.g. SIGHUP to reload, SIGUSR1 to dump state).
+pub fn signal_port(port: u16, protocol: Protocol, signal: Signal) -> KillResult {
+    // A single protocol always resolves to exactly one target per port
+    signal_ports(&[port], protocol.into(), signal).remove(0)
+}
+
+/// Send `signal` to the processes on all ports in the spec, from one snapshot. Returns one
+/// result per port and protocol, skipping protocols with nothing listening like
+/// [`kill_ports_with`]. A process holding several of the ports is signalled once.
+pub fn signal_ports(ports: &[u16], protocols: ProtocolFilter, signal: Signal) -> Vec<KillResult> {
+    let targets = snapshot_targets(ports, protocols);
+    let pids = target_pids(&targets);
+    let sys = load_processes(&pids, false);
+    let outcomes: BTreeMap<u32, ProcessKill> = pids
+        .iter()
+        .map(|&pid| {
+            let proc_ref = sys.process(Pid::from_u32(pid));
+            let (success, message) = match proc_ref.map(|p| p.kill_with(signal)) {
+                None => (false, format!("process {} not found", pid)),
+                Some(None) => (false, format!("{} is not supported on this platform", signal_name(signal))),
+                Some(Some(false)) => (false, kill_failed_message()),
+                Some(Some(true)) => (true, format!("sent {}", signal_name(signal))),
+            };
+            let outcome = ProcessKill {
+                pid,
+                process_name: process_name(&sys, pid),
+                success,
+                message,
+                signal: success.then_some(signal),
+            };
+            (pid, outcome)
+        })
+        .collect();
+    targets
         .iter()
-        .map(|&port| {
-            let (success, message) = kill_port(port);
-            KillResult { port, success, message }
+        .map(|t| {
+            let label = port_label(t.port, t.protocol);
+            let pids = match &t.pids {
+                Ok(p) if p.is_empty() => return t.result(false, format!("{}: nothing listening", label), Vec::new()),
+                Ok(p) => p,
+                Err(e) => return t.result(false, format!("{}: {}", label, e), Vec::new()),
+            };
+            let processes: Vec<ProcessKill> = pids.iter().filter_map(|pid| outcomes.get(pid)).cloned().collect();
+            let delivered = processes.iter().filter(|p| p.success).count();
+            let success = delivered == processes.len();
+            let message = if success && delivered == 1 {
+                format!("{}: sent {}", label, signal_name(signal))
+            } else if success {
+                format!("{}: sent {} to {} processes", label, signal_name(signal), delivered)
+            } else if processes.len() == 1 {
+                format!("{}: {}", label, processes[0].message)
+            } else {
+                format!("{}: sent {} to {} of {} processes", label, signal_name(signal), delivered, processes.len())
+            };
+            t.result(success, message, processes)
         })
         .collect()
 }
 
 pub mod gui;
 
+mod tree;
+pub use tree::{TreeProcess, TreeScope};
+
+#[cfg(target_os = "linux")]
+mod proc_net;
+
 #[cfg(target_os = "macos")]
 pub mod installer_macos;
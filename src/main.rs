@@ -1,7 +1,12 @@
 //! CLI entrypoint for psweep.
 
 use clap::{Parser, Subcommand};
-use psweep::{kill_ports, list_ports, parse_port_spec};
+use psweep::{
+    kill_ports_with, list_ports, parse_duration, parse_port_spec, parse_signal, plan_kills, port_tree,
+    signal_name, signal_ports, KillOptions, KillResult, ProtocolFilter, Signal, TreeScope,
+};
+use std::io::{BufRead, IsTerminal, Write};
+use std::time::Duration;
 use std::process::ExitCode;
 
 #[derive(Parser)]
@@ -16,12 +21,49 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// List active ports and their processes
-    List,
+    List {
+        /// Protocol(s) to list: tcp, udp or both
+        #[arg(long, default_value = "both")]
+        protocol: ProtocolFilter,
+    },
     /// Kill process(es) on the given port(s). Supports: 3000, 3000,8000, 9000-9010
     Kill {
         /// Port(s) to kill: single port, comma-separated, or range (e.g. 3000,8000,9000-9010)
         #[arg(required = true)]
         ports: String,
+        /// Protocol(s) to kill: tcp, udp or both
+        #[arg(long, default_value = "tcp")]
+        protocol: ProtocolFilter,
+        /// How long to wait after SIGTERM before sending SIGKILL (e.g. 500ms, 3s, 1m)
+        #[arg(long, default_value = "3s", value_parser = parse_duration)]
+        grace: Duration,
+        /// Signal to send first, before escalating to SIGKILL (e.g. INT, QUIT)
+        #[arg(long, default_value = "TERM", value_parser = parse_signal)]
+        signal: Signal,
+        /// Send SIGKILL immediately instead of SIGTERM first
+        #[arg(long, conflicts_with_all = ["grace", "signal"])]
+        force: bool,
+        /// Also stop the process tree: group (process group), session, or ancestor (topmost non-shell parent)
+        #[arg(long, value_name = "SCOPE")]
+        tree: Option<TreeScope>,
+        /// Don't ask for confirmation before a tree kill
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Only show which processes would be signalled; don't touch anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Send a signal to the process(es) on the given port(s) without waiting for them to exit
+    Signal {
+        /// Signal to send: HUP, INT, USR1, ... (with or without the SIG prefix)
+        #[arg(value_parser = parse_signal)]
+        signal: Signal,
+        /// Port(s) to signal: single port, comma-separated, or range (e.g. 3000,8000,9000-9010)
+        #[arg(required = true)]
+        ports: String,
+        /// Protocol(s) to signal: tcp, udp or both
+        #[arg(long, default_value = "tcp")]
+        protocol: ProtocolFilter,
     },
     /// Open the Port Sweeper GUI
     Gui,
@@ -46,23 +88,42 @@ fn run(cli: Cli) -> anyhow::Result<()> {
             }
             run_gui()
         }
-        Some(Commands::List) => run_list(),
-        Some(Commands::Kill { ports }) => run_kill(&ports),
+        Some(Commands::List { protocol }) => run_list(protocol),
+        Some(Commands::Kill { ports, protocol, grace, signal, force, tree, yes, dry_run }) => {
+            let opts = KillOptions { signal, grace, force, tree };
+            if dry_run {
+                run_dry_run(&ports, protocol, &opts)
+            } else {
+                run_kill(&ports, protocol, &opts, yes)
+            }
+        }
+        Some(Commands::Signal { signal, ports, protocol }) => run_signal(signal, &ports, protocol),
         Some(Commands::Gui) => run_gui(),
     }
 }
 
-fn run_list() -> anyhow::Result<()> {
-    let entries = list_ports().map_err(anyhow::Error::msg)?;
+fn run_list(protocols: ProtocolFilter) -> anyhow::Result<()> {
+    let entries = list_ports(protocols).map_err(anyhow::Error::msg)?;
     if entries.is_empty() {
         println!("No listening ports found.");
         return Ok(());
     }
     // Table header
-    println!("{:>6}  {:<24}  {:>6}  {:<10}", "Port", "Process", "PID", "Status");
-    println!("{}", "-".repeat(52));
+    println!(
+        "{:>6}  {:<5}  {:<15}  {:<24}  {:>6}  {:<10}",
+        "Port", "Proto", "Address", "Process", "PID", "Status"
+    );
+    println!("{}", "-".repeat(76));
     for e in &entries {
-        println!("{:>6}  {:<24}  {:>6}  {:<10}", e.port, truncate(&e.process_name, 24), e.pid, e.status);
+        println!(
+            "{:>6}  {:<5}  {:<15}  {:<24}  {:>6}  {:<10}",
+            e.port,
+            e.protocol,
+            e.bind_addr,
+            truncate(&e.process_name, 24),
+            e.pid,
+            e.status
+        );
     }
     Ok(())
 }
@@ -76,20 +137,115 @@ fn truncate(s: &str, max: usize) -> String {
     }
 }
 
-fn run_kill(ports_spec: &str) -> anyhow::Result<()> {
+fn run_kill(ports_spec: &str, protocols: ProtocolFilter, opts: &KillOptions, yes: bool) -> anyhow::Result<()> {
+    let ports = parse_port_spec(ports_spec).map_err(anyhow::Error::msg)?;
+    if ports.is_empty() {
+        anyhow::bail!("No ports specified");
+    }
+    if let Some(scope) = opts.tree {
+        let any = print_trees(&ports, protocols, scope)?;
+        if any && !yes && !confirm("Stop all of these processes?")? {
+            anyhow::bail!("Aborted");
+        }
+    }
+    report(&kill_ports_with(&ports, protocols, opts))
+}
+
+fn run_dry_run(ports_spec: &str, protocols: ProtocolFilter, opts: &KillOptions) -> anyhow::Result<()> {
+    let ports = parse_port_spec(ports_spec).map_err(anyhow::Error::msg)?;
+    if ports.is_empty() {
+        anyhow::bail!("No ports specified");
+    }
+    let plan = plan_kills(&ports, protocols, opts).map_err(anyhow::Error::msg)?;
+    if plan.is_empty() {
+        println!("Nothing listening on the given port(s); nothing would be killed.");
+        return Ok(());
+    }
+    println!(
+        "{:>6}  {:<5}  {:>6}  {:<24}  {:<12}  {:<8}",
+        "Port", "Proto", "PID", "Process", "User", "Signal"
+    );
+    println!("{}", "-".repeat(70));
+    for p in &plan {
+        println!(
+            "{:>6}  {:<5}  {:>6}  {:<24}  {:<12}  {:<8}",
+            p.port,
+            p.protocol,
+            p.pid,
+            truncate(&p.process_name, 24),
+            truncate(&p.user, 12),
+            signal_name(p.signal)
+        );
+    }
+    let free: Vec<String> = ports
+        .iter()
+        .filter(|port| !plan.iter().any(|p| p.port == **port))
+        .map(|port| port.to_string())
+        .collect();
+    if !free.is_empty() && free.len() <= 20 {
+        println!("Nothing listening on: {}", free.join(", "));
+    } else if !free.is_empty() {
+        println!("Nothing listening on {} other port(s)", free.len());
+    }
+    println!("Dry run: nothing was killed.");
+    Ok(())
+}
+
+/// Print the process tree a tree kill would stop on each port. Returns false if empty.
+fn print_trees(ports: &[u16], protocols: ProtocolFilter, scope: TreeScope) -> anyhow::Result<bool> {
+    let mut any = false;
+    for &port in ports {
+        for &protocol in protocols.protocols() {
+            let tree = port_tree(port, protocol, scope).map_err(anyhow::Error::msg)?;
+            if tree.is_empty() {
+                continue;
+            }
+            any = true;
+            println!("Port {}/{}:", port, protocol.to_string().to_lowercase());
+            for p in &tree {
+                let marker = if p.owns_port { "  <- listening" } else { "" };
+                println!("  {}{} ({}){}", "  ".repeat(p.depth), p.process_name, p.pid, marker);
+            }
+        }
+    }
+    Ok(any)
+}
+
+/// Ask a yes/no question on the terminal. Without a terminal, refuse instead of guessing.
+fn confirm(question: &str) -> anyhow::Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!("{} Refusing without a terminal; pass --yes to confirm", question);
+    }
+    print!("{} [y/N] ", question);
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+fn run_signal(signal: Signal, ports_spec: &str, protocols: ProtocolFilter) -> anyhow::Result<()> {
     let ports = parse_port_spec(ports_spec).map_err(anyhow::Error::msg)?;
     if ports.is_empty() {
         anyhow::bail!("No ports specified");
     }
-    let results = kill_ports(&ports);
+    report(&signal_ports(&ports, protocols, signal))
+}
+
+/// Print one line per port (plus per-process lines when a port had several owners).
+fn report(results: &[KillResult]) -> anyhow::Result<()> {
     let mut failed = 0;
-    for r in &results {
+    for r in results {
         if r.success {
             println!("{}", r.message);
         } else {
             eprintln!("{}", r.message);
             failed += 1;
         }
+        if r.processes.len() > 1 {
+            for p in &r.processes {
+                println!("  pid {:>6}  {:<24}  {}", p.pid, truncate(&p.process_name, 24), p.message);
+            }
+        }
     }
     if failed > 0 {
         anyhow::bail!("{} port(s) failed", failed);
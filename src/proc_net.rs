@@ -0,0 +1,113 @@
+//! Linux: parse the kernel socket tables in /proc/net (tcp, tcp6, udp, udp6).
+//! The `listeners` crate reads the same files but drops the socket state, which we need
+//! to tell listening sockets apart from established connections.
+
+use crate::Protocol;
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+
+/// TCP state code for LISTEN in /proc/net/tcp{,6} (see include/net/tcp_states.h).
+pub(crate) const TCP_LISTEN: u8 = 0x0A;
+
+/// One row of a /proc/net socket table.
+#[derive(Debug, Clone)]
+pub(crate) struct SocketRow {
+    pub local: SocketAddr,
+    pub state: u8,
+    pub inode: u64,
+}
+
+/// Read the IPv4 and IPv6 tables for `protocol` under `net_dir` (normally "/proc/net").
+/// A missing table (e.g. IPv6 disabled) is skipped rather than treated as an error.
+pub(crate) fn read_tables(net_dir: &Path, protocol: Protocol) -> Result<Vec<SocketRow>, String> {
+    let names: [&str; 2] = match protocol {
+        Protocol::Tcp => ["tcp", "tcp6"],
+        Protocol::Udp => ["udp", "udp6"],
+    };
+    let mut rows = Vec::new();
+    for name in names {
+        let path = net_dir.join(name);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        // First line is the column header
+        rows.extend(content.lines().skip(1).filter_map(parse_row));
+    }
+    Ok(rows)
+}
+
+/// Parse "  sl  local_address rem_address   st tx_queue:rx_queue ...".
+fn parse_row(line: &str) -> Option<SocketRow> {
+    let mut fields = line.split_whitespace();
+    let _sl = fields.next()?;
+    let local = parse_socket_addr(fields.next()?)?;
+    let _remote = fields.next()?;
+    let state = u8::from_str_radix(fields.next()?, 16).ok()?;
+    // tx_queue:rx_queue, tr:tm->when, retrnsmt, uid, timeout
+    let inode = fields.nth(5)?.parse().ok()?;
+    Some(SocketRow { local, state, inode })
+}
+
+/// Map each socket inode in `inodes` to every pid holding it open, by scanning
+/// `<proc_root>/<pid>/fd`. Forked workers share one listening socket, so an inode can
+/// have several owners. Processes we may not inspect are skipped.
+pub(crate) fn socket_owners(proc_root: &Path, inodes: &BTreeSet<u64>) -> BTreeMap<u64, Vec<u32>> {
+    let mut owners = BTreeMap::<u64, Vec<u32>>::new();
+    if inodes.is_empty() {
+        return owners;
+    }
+    let Ok(dir) = std::fs::read_dir(proc_root) else {
+        return owners;
+    };
+    for entry in dir.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            let Ok(target) = std::fs::read_link(fd.path()) else {
+                continue;
+            };
+            let Some(inode) = socket_inode(&target.to_string_lossy()) else {
+                continue;
+            };
+            if inodes.contains(&inode) {
+                let pids = owners.entry(inode).or_default();
+                if !pids.contains(&pid) {
+                    pids.push(pid);
+                }
+            }
+        }
+    }
+    owners
+}
+
+/// "socket:[12345]" -> 12345
+fn socket_inode(link: &str) -> Option<u64> {
+    link.strip_prefix("socket:[")?.strip_suffix(']')?.parse().ok()
+}
+
+/// Parse "0100007F:1F90" (IPv4) or a 32-hex-digit IPv6 address with port. The kernel prints
+/// each 32-bit word of the address in host byte order, the port in big-endian hex.
+fn parse_socket_addr(s: &str) -> Option<SocketAddr> {
+    let (addr, port) = s.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let ip = match addr.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(u32::from_str_radix(addr, 16).ok()?.to_ne_bytes())),
+        32 => {
+            let mut bytes = [0u8; 16];
+            for (i, chunk) in bytes.chunks_mut(4).enumerate() {
+                let word = u32::from_str_radix(&addr[i * 8..i * 8 + 8], 16).ok()?;
+                chunk.copy_from_slice(&word.to_ne_bytes());
+            }
+            IpAddr::V6(Ipv6Addr::from(bytes))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
@@ -0,0 +1,185 @@
+//! Process trees behind a listener: its process group, its session, or its topmost non-shell
+//! ancestor, always together with every descendant of the listener itself.
+//! Used by tree-kill so a supervisor (npm, cargo watch, ...) cannot respawn the killed child.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+use sysinfo::{Pid, System};
+
+/// Which processes around a listener to stop along with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeScope {
+    /// Every process in the listener's process group (Unix only).
+    Group,
+    /// Every process in the listener's session (Unix only).
+    Session,
+    /// The topmost ancestor that is not a shell, and everything below it.
+    Ancestor,
+}
+
+impl FromStr for TreeScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "group" | "pgid" => Ok(TreeScope::Group),
+            "session" | "sid" => Ok(TreeScope::Session),
+            "ancestor" | "parent" => Ok(TreeScope::Ancestor),
+            other => Err(format!("invalid tree scope: {} (expected group, session or ancestor)", other)),
+        }
+    }
+}
+
+/// One process in the affected tree, in depth-first order (parents before children).
+#[derive(Debug, Clone)]
+pub struct TreeProcess {
+    pub pid: u32,
+    pub parent: Option<u32>,
+    pub process_name: String,
+    /// Nesting level below the tree's root(s), for indented display.
+    pub depth: usize,
+    /// True for the process(es) that own the listening socket.
+    pub owns_port: bool,
+}
+
+/// Shells and terminal plumbing we stop climbing at: killing these would take down the
+/// user's terminal rather than the dev server.
+const SHELLS: &[&str] = &[
+    "sh", "bash", "zsh", "fish", "dash", "ksh", "mksh", "tcsh", "csh", "nu", "elvish", "xonsh",
+    "pwsh", "powershell.exe", "pwsh.exe", "cmd.exe", "explorer.exe", "login", "sshd", "tmux",
+    "screen", "init", "systemd", "launchd",
+];
+
+fn is_shell(name: &str) -> bool {
+    let name = name.trim_start_matches('-');
+    SHELLS.iter().any(|s| s.eq_ignore_ascii_case(name))
+}
+
+/// Expand `listeners` into the tree selected by `scope`. Never includes pid 1, psweep itself,
+/// or any of psweep's ancestors (which would kill the terminal running it).
+pub(crate) fn expand(sys: &System, listeners: &[u32], scope: TreeScope) -> Result<Vec<TreeProcess>, String> {
+    let mut children = BTreeMap::<u32, Vec<u32>>::new();
+    for (pid, p) in sys.processes() {
+        // On Linux sysinfo also lists threads; they die with their process
+        if p.thread_kind().is_some() {
+            continue;
+        }
+        if let Some(parent) = p.parent() {
+            children.entry(parent.as_u32()).or_default().push(pid.as_u32());
+        }
+    }
+    for kids in children.values_mut() {
+        kids.sort_unstable();
+    }
+    let parent_of = |pid: u32| sys.process(Pid::from_u32(pid)).and_then(|p| p.parent()).map(|p| p.as_u32());
+    let name_of = |pid: u32| {
+        sys.process(Pid::from_u32(pid))
+            .map(|p| p.name().to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+
+    let mut members = BTreeSet::new();
+    for &pid in listeners {
+        members.insert(pid);
+        match scope {
+            TreeScope::Group => {
+                let pgid = process_group(pid)?;
+                for (other, p) in sys.processes() {
+                    if p.thread_kind().is_none() && process_group(other.as_u32()).ok() == Some(pgid) {
+                        members.insert(other.as_u32());
+                    }
+                }
+            }
+            TreeScope::Session => {
+                let sid = sys
+                    .process(Pid::from_u32(pid))
+                    .and_then(|p| p.session_id())
+                    .ok_or_else(|| format!("session of process {} is unknown on this platform", pid))?;
+                for (other, p) in sys.processes() {
+                    if p.thread_kind().is_none() && p.session_id() == Some(sid) {
+                        members.insert(other.as_u32());
+                    }
+                }
+            }
+            TreeScope::Ancestor => {
+                let mut top = pid;
+                while let Some(parent) = parent_of(top) {
+                    if parent <= 1 || is_shell(&name_of(parent)) {
+                        break;
+                    }
+                    top = parent;
+                }
+                collect_subtree(top, &children, &mut members);
+            }
+        }
+        collect_subtree(pid, &children, &mut members);
+    }
+
+    // Never take down ourselves or the shell/terminal we were started from
+    let mut protected = BTreeSet::from([0, 1, std::process::id()]);
+    let mut cur = std::process::id();
+    while let Some(parent) = parent_of(cur) {
+        if !protected.insert(parent) {
+            break;
+        }
+        cur = parent;
+    }
+    members.retain(|pid| !protected.contains(pid));
+
+    // Depth-first from the roots (members whose parent is outside the set)
+    let listener_set: BTreeSet<u32> = listeners.iter().copied().collect();
+    let roots: Vec<u32> = members
+        .iter()
+        .copied()
+        .filter(|&pid| parent_of(pid).is_none_or(|p| !members.contains(&p)))
+        .collect();
+    let mut out = Vec::with_capacity(members.len());
+    let mut stack: Vec<(u32, usize)> = roots.into_iter().rev().map(|pid| (pid, 0)).collect();
+    while let Some((pid, depth)) = stack.pop() {
+        out.push(TreeProcess {
+            pid,
+            parent: parent_of(pid),
+            process_name: name_of(pid),
+            depth,
+            owns_port: listener_set.contains(&pid),
+        });
+        if let Some(kids) = children.get(&pid) {
+            for &kid in kids.iter().rev() {
+                if members.contains(&kid) {
+                    stack.push((kid, depth + 1));
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn collect_subtree(pid: u32, children: &BTreeMap<u32, Vec<u32>>, out: &mut BTreeSet<u32>) {
+    let mut seen = BTreeSet::new();
+    let mut stack = vec![pid];
+    while let Some(p) = stack.pop() {
+        if !seen.insert(p) {
+            continue;
+        }
+        out.insert(p);
+        if let Some(kids) = children.get(&p) {
+            stack.extend(kids.iter().copied());
+        }
+    }
+}
+
+#[cfg(unix)]
+fn process_group(pid: u32) -> Result<u32, String> {
+    // SAFETY: getpgid only reads kernel state for the given pid.
+    let pgid = unsafe { libc::getpgid(pid as libc::pid_t) };
+    if pgid < 0 {
+        Err(format!("process group of {}: {}", pid, std::io::Error::last_os_error()))
+    } else {
+        Ok(pgid as u32)
+    }
+}
+
+#[cfg(not(unix))]
+fn process_group(_pid: u32) -> Result<u32, String> {
+    Err("process groups are not supported on this platform".to_string())
+}